@@ -1,26 +1,102 @@
+use crate::oauth;
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use core::fmt;
-use futures::future::{ready, Fuse, FusedFuture};
+use futures::future::{Fuse, FusedFuture};
 use futures::prelude::*;
 use parse_display::Display;
 use reqwest::Client;
 use serde::de::DeserializeOwned;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use url::Url;
 
 #[derive(Clone, Copy, Debug, Display, PartialEq, Eq, Hash)]
 #[display(style = "snake_case")]
 pub enum TweetFields {
     CreatedAt,
+    ReferencedTweets,
+    Entities,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Resources to expand inline via the response's `includes`, see
+/// [`GetTweetOpts`] and [`ApiData`].
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq, Hash)]
+pub enum Expansion {
+    #[display("referenced_tweets.id")]
+    ReferencedTweetsId,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferencedTweetType {
+    Retweeted,
+    Quoted,
+    RepliedTo,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReferencedTweet {
+    #[serde(rename = "type")]
+    pub kind: ReferencedTweetType,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Tweet {
     pub id: String,
     pub text: String,
     pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub referenced_tweets: Vec<ReferencedTweet>,
+    pub entities: Option<Entities>,
+}
+
+impl Tweet {
+    /// This tweet's effective text: a retweet's truncated text is replaced
+    /// with the original (via `includes`), a quoted tweet's text is
+    /// appended, and HTML entities are unescaped.
+    pub fn full_text(&self, includes: &HashMap<String, Tweet>) -> String {
+        for reference in &self.referenced_tweets {
+            if reference.kind == ReferencedTweetType::Retweeted {
+                if let Some(original) = includes.get(&reference.id) {
+                    return unescape_html_entities(&original.text);
+                }
+            }
+        }
+
+        let mut text = unescape_html_entities(&self.text);
+        for reference in &self.referenced_tweets {
+            if reference.kind == ReferencedTweetType::Quoted {
+                if let Some(quoted) = includes.get(&reference.id) {
+                    text.push_str("\n\nQuoting: ");
+                    text.push_str(&unescape_html_entities(&quoted.text));
+                }
+            }
+        }
+        text
+    }
+}
+
+fn unescape_html_entities(s: &str) -> String {
+    s.replace("&gt;", ">")
+        .replace("&lt;", "<")
+        .replace("&amp;", "&")
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Entities {
+    #[serde(default)]
+    pub urls: Vec<UrlEntity>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UrlEntity {
+    pub url: String,
+    pub expanded_url: String,
+    pub display_url: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -30,14 +106,30 @@ pub struct User {
     pub username: String,
 }
 
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct LikeResult {
+    pub liked: bool,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct FollowResult {
+    pub following: bool,
+    #[serde(default)]
+    pub pending_follow: bool,
+}
+
 #[derive(Debug, Clone, Builder)]
 pub struct GetTweetOpts {
     #[builder(default)]
     tweet_fields: HashSet<TweetFields>,
     #[builder(default)]
+    expansions: HashSet<Expansion>,
+    #[builder(default)]
     max_results: Option<usize>,
     #[builder(default)]
     end_time: Option<DateTime<Utc>>,
+    #[builder(default)]
+    until_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -49,18 +141,67 @@ struct ApiMeta {
     previous_token: Option<String>,
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ApiIncludes {
+    #[serde(default)]
+    tweets: Vec<Tweet>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 struct ApiData<T> {
     data: Option<T>,
+    #[serde(default)]
+    includes: ApiIncludes,
     meta: Option<ApiMeta>,
 }
 
+/// One entry of Twitter's JSON error body, in either its list form
+/// (`{"errors": [...]}`, used by v2 endpoints) or its flat form
+/// (`{"title", "detail", "status"}`, used elsewhere).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TwitterErrorMessage {
+    pub title: Option<String>,
+    pub detail: Option<String>,
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum TwitterErrorEnvelope {
+    List { errors: Vec<TwitterErrorMessage> },
+    Single(TwitterErrorMessage),
+}
+
+impl TwitterErrorEnvelope {
+    fn into_messages(self) -> Vec<TwitterErrorMessage> {
+        match self {
+            TwitterErrorEnvelope::List { errors } => errors,
+            TwitterErrorEnvelope::Single(message) => vec![message],
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum ApiError {
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
     #[error(transparent)]
     Url(#[from] url::ParseError),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// A non-2xx response Twitter explained with an error body, carrying the
+    /// HTTP status and whatever `title`/`detail` messages it parsed out.
+    #[error("twitter api error ({status}): {messages:?}")]
+    Twitter {
+        status: reqwest::StatusCode,
+        messages: Vec<TwitterErrorMessage>,
+    },
+    /// HTTP 429, carrying the `x-rate-limit-reset` epoch (when the response
+    /// included one) so callers (and the stream's reconnect/backoff logic)
+    /// can wait the exact required duration instead of guessing.
+    #[error("rate limited, reset at unix timestamp {reset:?}")]
+    RateLimited { reset: Option<u64> },
 }
 
 pub type Result<T, E = ApiError> = core::result::Result<T, E>;
@@ -86,11 +227,109 @@ impl fmt::Display for BearerToken {
     }
 }
 
+/// A consumer key/secret plus the `oauth_token`/`oauth_token_secret` pair
+/// obtained by completing the PIN (out-of-band) authorization flow, see
+/// [`Api::request_token`] and [`Api::access_token`]. Unlike [`BearerToken`]
+/// this lets requests act on behalf of a user rather than the app alone.
+#[derive(Clone)]
+pub struct UserCredentials {
+    consumer_key: String,
+    consumer_secret: String,
+    oauth_token: String,
+    oauth_token_secret: String,
+}
+
+impl fmt::Debug for UserCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UserCredentials").finish()
+    }
+}
+
+impl UserCredentials {
+    fn oauth_credentials(&self) -> oauth::Credentials {
+        oauth::Credentials {
+            consumer_key: self.consumer_key.clone(),
+            consumer_secret: self.consumer_secret.clone(),
+            token: Some(self.oauth_token.clone()),
+            token_secret: Some(self.oauth_token_secret.clone()),
+        }
+    }
+}
+
+/// A temporary token returned by `/oauth/request_token`, good only for
+/// building the authorize URL and redeeming the user's PIN at
+/// `/oauth/access_token`.
+#[derive(Clone, Debug)]
+pub struct RequestToken {
+    consumer_key: String,
+    consumer_secret: String,
+    oauth_token: String,
+    oauth_token_secret: String,
+}
+
+#[derive(Clone, Debug)]
+pub enum Auth {
+    BearerToken(BearerToken),
+    UserCredentials(UserCredentials),
+}
+
+impl From<BearerToken> for Auth {
+    fn from(token: BearerToken) -> Self {
+        Auth::BearerToken(token)
+    }
+}
+
+impl From<UserCredentials> for Auth {
+    fn from(credentials: UserCredentials) -> Self {
+        Auth::UserCredentials(credentials)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Api {
     base_url: Url,
     client: Client,
-    token: BearerToken,
+    auth: Auth,
+}
+
+/// Builds a request against `url`, signed with whichever auth `api` holds.
+fn signed_request(api: &Api, method: reqwest::Method, url: Url) -> reqwest::RequestBuilder {
+    match &api.auth {
+        Auth::BearerToken(token) => api.client.request(method, url).bearer_auth(token),
+        Auth::UserCredentials(credentials) => {
+            let header = oauth::authorization_header(
+                method.as_str(),
+                &url,
+                &[],
+                &credentials.oauth_credentials(),
+            );
+            api.client.request(method, url).header("Authorization", header)
+        }
+    }
+}
+
+/// Turns a non-2xx response into the appropriate [`ApiError`]: an
+/// [`ApiError::RateLimited`] carrying the reset epoch for HTTP 429, or an
+/// [`ApiError::Twitter`] carrying whatever Twitter's JSON error body
+/// explained otherwise.
+async fn response_error(res: reqwest::Response) -> ApiError {
+    if res.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let reset = res
+            .headers()
+            .get("x-rate-limit-reset")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok());
+        return ApiError::RateLimited { reset };
+    }
+    let status = res.status();
+    let messages = res
+        .text()
+        .await
+        .ok()
+        .and_then(|body| serde_json::from_str::<TwitterErrorEnvelope>(&body).ok())
+        .map(TwitterErrorEnvelope::into_messages)
+        .unwrap_or_default();
+    ApiError::Twitter { status, messages }
 }
 
 #[pin_project]
@@ -103,22 +342,26 @@ where
     T: DeserializeOwned + 'static,
 {
     fn get(api: &Api, url: Url) -> Self {
-        // println!(r#"http {} "Authorization:Bearer {}""#, url, api.token);
+        Self::request(api, reqwest::Method::GET, url, None::<&()>)
+    }
+
+    /// Shared by every endpoint: signs the request with whichever auth `api`
+    /// holds, attaches `body` as JSON when given, and deserializes the
+    /// `{ "data": { ... } }` response envelope.
+    fn request(api: &Api, method: reqwest::Method, url: Url, body: Option<&impl Serialize>) -> Self {
+        let mut req = signed_request(api, method, url);
+        if let Some(body) = body {
+            req = req.json(body);
+        }
         Self {
-            future: api
-                .client
-                .get(url)
-                .bearer_auth(&api.token)
-                .send()
-                // TODO this can be improved upon by providing more detail from the payload
-                .and_then(|res| {
-                    // println!("{:#?}", res);
-                    ready(res.error_for_status())
-                })
-                .and_then(|res| res.json::<ApiData<T>>())
-                // .inspect_ok(|res| println!("{:#?}", res.meta))
-                .err_into()
-                .boxed(),
+            future: async move {
+                let res = req.send().await?;
+                if !res.status().is_success() {
+                    return Err(response_error(res).await);
+                }
+                Ok(res.json::<ApiData<T>>().await?)
+            }
+            .boxed(),
         }
     }
 
@@ -142,6 +385,7 @@ pub struct ApiResults<T> {
     result: Fuse<ApiResult<Vec<T>>>,
     items: std::vec::IntoIter<T>,
     pagination_token: Option<String>,
+    includes: HashMap<String, Tweet>,
 }
 
 impl<T> ApiResults<T>
@@ -156,8 +400,16 @@ where
             result,
             items: vec![].into_iter(),
             pagination_token: None,
+            includes: HashMap::new(),
         }
     }
+
+    /// Tweets pulled in via `expansions=referenced_tweets.id` on the most
+    /// recently fetched page, keyed by id. Used to resolve a yielded tweet's
+    /// [`Tweet::full_text`].
+    pub fn includes(&self) -> &HashMap<String, Tweet> {
+        &self.includes
+    }
 }
 
 impl<T> Stream for ApiResults<T>
@@ -171,6 +423,7 @@ where
         let url = this.url;
         let items = this.items;
         let pagination_token = this.pagination_token;
+        let includes = this.includes;
 
         if let Some(item) = items.next() {
             return Poll::Ready(Some(Ok(item)));
@@ -192,6 +445,7 @@ where
             Poll::Ready(res) => match res {
                 Ok(data) => {
                     *pagination_token = data.meta.and_then(|meta| meta.next_token);
+                    includes.extend(data.includes.tweets.into_iter().map(|t| (t.id.clone(), t)));
                     *items = data.data.unwrap_or_default().into_iter();
                     Poll::Ready(items.next().map(Ok))
                 }
@@ -202,13 +456,257 @@ where
     }
 }
 
+/// Backoff between reconnect attempts starts here and doubles on every
+/// consecutive failure, capped at [`STREAM_BACKOFF_MAX`].
+const STREAM_BACKOFF_INITIAL: Duration = Duration::from_secs(1);
+const STREAM_BACKOFF_MAX: Duration = Duration::from_secs(32);
+/// A connection that stays open this long without erroring is considered
+/// healthy again, so the backoff resets to [`STREAM_BACKOFF_INITIAL`].
+const STREAM_HEALTHY_AFTER: Duration = Duration::from_secs(60);
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T>>>;
+type BoxByteStream = Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>>>>;
+
+enum StreamState {
+    Connecting(BoxFuture<Result<reqwest::Response>>),
+    Reading(BoxByteStream),
+    Backoff(Pin<Box<tokio::time::Sleep>>),
+    /// A connect attempt failed in a way retrying won't fix (e.g. bad/revoked
+    /// credentials). Yielded to the consumer once, then the stream ends.
+    Failed(Option<ApiError>),
+}
+
+/// How long to sleep before retrying after [`ApiError::RateLimited`]: until
+/// the given reset epoch, or [`STREAM_BACKOFF_MAX`] if Twitter didn't send
+/// one (never less, since treating an unknown reset as already-expired would
+/// just retry immediately into the same rate limit).
+fn rate_limit_wait(reset: Option<u64>) -> Duration {
+    let reset = match reset {
+        Some(reset) => reset,
+        None => return STREAM_BACKOFF_MAX,
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    Duration::from_secs(reset.saturating_sub(now))
+}
+
+/// A long-lived connection to a filtered-stream endpoint that yields one `T`
+/// per newline-delimited JSON object. Dropped connections (EOF or a
+/// transport error) are reconnected automatically with exponential backoff,
+/// the same way a userstream consumer has to stay alive across hiccups. A
+/// 429 instead waits out the rate limit rather than backing off blindly, and
+/// a client error (bad/revoked credentials, malformed query) ends the stream
+/// with that error rather than retrying forever.
+#[pin_project]
+pub struct StreamResults<T> {
+    api: Api,
+    url: Url,
+    state: StreamState,
+    buf: Vec<u8>,
+    backoff: Duration,
+    connected_at: Option<std::time::Instant>,
+    _marker: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> StreamResults<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    fn connect(api: Api, url: Url) -> Self {
+        Self {
+            state: StreamState::Connecting(Self::connect_future(&api, &url)),
+            api,
+            url,
+            buf: Vec::new(),
+            backoff: STREAM_BACKOFF_INITIAL,
+            connected_at: None,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    fn connect_future(api: &Api, url: &Url) -> BoxFuture<Result<reqwest::Response>> {
+        let req = signed_request(api, reqwest::Method::GET, url.clone());
+        async move {
+            let res = req.send().await?;
+            if !res.status().is_success() {
+                return Err(response_error(res).await);
+            }
+            Ok(res)
+        }
+        .boxed()
+    }
+
+    /// Pulls one complete line (if any) out of the buffer and deserializes
+    /// it, skipping blank keep-alive lines the stream sends between tweets.
+    fn take_line(buf: &mut Vec<u8>) -> Option<Result<T>> {
+        loop {
+            let newline = buf.iter().position(|b| *b == b'\n')?;
+            let line: Vec<u8> = buf.drain(..=newline).collect();
+            let line = &line[..line.len() - 1];
+            if line.is_empty() {
+                continue;
+            }
+            match serde_json::from_slice::<ApiData<T>>(line).map_err(ApiError::from) {
+                Ok(ApiData { data: Some(item), .. }) => return Some(Ok(item)),
+                Ok(ApiData { data: None, .. }) => continue,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+impl<T> Stream for StreamResults<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    type Item = Result<T>;
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        loop {
+            if let Some(item) = Self::take_line(this.buf) {
+                return Poll::Ready(Some(item));
+            }
+
+            if let StreamState::Failed(err) = this.state {
+                return Poll::Ready(err.take().map(Err));
+            }
+
+            let next_state = match this.state {
+                StreamState::Connecting(future) => match future.as_mut().poll(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Ok(res)) => {
+                        *this.connected_at = Some(std::time::Instant::now());
+                        StreamState::Reading(res.bytes_stream().boxed())
+                    }
+                    Poll::Ready(Err(err)) => match &err {
+                        ApiError::RateLimited { reset } => {
+                            StreamState::Backoff(Box::pin(tokio::time::sleep(rate_limit_wait(*reset))))
+                        }
+                        ApiError::Twitter { status, .. } if status.is_client_error() => {
+                            StreamState::Failed(Some(err))
+                        }
+                        _ => {
+                            let backoff = *this.backoff;
+                            *this.backoff = (backoff * 2).min(STREAM_BACKOFF_MAX);
+                            StreamState::Backoff(Box::pin(tokio::time::sleep(backoff)))
+                        }
+                    },
+                },
+                StreamState::Reading(body) => match body.as_mut().poll_next(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Some(Ok(chunk))) => {
+                        if this
+                            .connected_at
+                            .map(|at| at.elapsed() > STREAM_HEALTHY_AFTER)
+                            .unwrap_or(false)
+                        {
+                            *this.backoff = STREAM_BACKOFF_INITIAL;
+                        }
+                        this.buf.extend_from_slice(&chunk);
+                        continue;
+                    }
+                    Poll::Ready(Some(Err(_))) | Poll::Ready(None) => {
+                        let backoff = *this.backoff;
+                        *this.backoff = (backoff * 2).min(STREAM_BACKOFF_MAX);
+                        this.buf.clear();
+                        StreamState::Backoff(Box::pin(tokio::time::sleep(backoff)))
+                    }
+                },
+                StreamState::Backoff(sleep) => match sleep.as_mut().poll(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(()) => StreamState::Connecting(Self::connect_future(this.api, this.url)),
+                },
+            };
+            *this.state = next_state;
+        }
+    }
+}
+
 impl Api {
-    pub fn new(token: BearerToken) -> Self {
+    pub fn new(auth: impl Into<Auth>) -> Self {
         Self {
             base_url: Url::parse("https://api.twitter.com/").unwrap(),
             client: Client::new(),
-            token,
+            auth: auth.into(),
+        }
+    }
+
+    /// Step one of the PIN (out-of-band) flow: obtain a temporary token good
+    /// for building the authorize URL the user must visit.
+    pub async fn request_token(consumer_key: &str, consumer_secret: &str) -> Result<RequestToken> {
+        let url = Url::parse("https://api.twitter.com/oauth/request_token").unwrap();
+        let credentials = oauth::Credentials {
+            consumer_key: consumer_key.to_owned(),
+            consumer_secret: consumer_secret.to_owned(),
+            token: None,
+            token_secret: None,
+        };
+        let header =
+            oauth::authorization_header("POST", &url, &[("oauth_callback", "oob")], &credentials);
+        let res = Client::new()
+            .post(url)
+            .header("Authorization", header)
+            .send()
+            .await?;
+        if !res.status().is_success() {
+            return Err(response_error(res).await);
         }
+        let params = parse_form_urlencoded(&res.text().await?);
+        Ok(RequestToken {
+            consumer_key: consumer_key.to_owned(),
+            consumer_secret: consumer_secret.to_owned(),
+            oauth_token: params.get("oauth_token").cloned().unwrap_or_default(),
+            oauth_token_secret: params
+                .get("oauth_token_secret")
+                .cloned()
+                .unwrap_or_default(),
+        })
+    }
+
+    /// The URL the user should visit to approve the app and receive a PIN.
+    pub fn authorize_url(request_token: &RequestToken) -> Url {
+        let mut url = Url::parse("https://api.twitter.com/oauth/authorize").unwrap();
+        url.query_pairs_mut()
+            .append_pair("oauth_token", &request_token.oauth_token);
+        url
+    }
+
+    /// Step two of the PIN flow: redeem the PIN (`oauth_verifier`) the user
+    /// read off the authorize page for durable user credentials.
+    pub async fn access_token(request_token: RequestToken, pin: &str) -> Result<UserCredentials> {
+        let url = Url::parse("https://api.twitter.com/oauth/access_token").unwrap();
+        let credentials = oauth::Credentials {
+            consumer_key: request_token.consumer_key.clone(),
+            consumer_secret: request_token.consumer_secret.clone(),
+            token: Some(request_token.oauth_token.clone()),
+            token_secret: Some(request_token.oauth_token_secret.clone()),
+        };
+        let header = oauth::authorization_header(
+            "POST",
+            &url,
+            &[("oauth_verifier", pin)],
+            &credentials,
+        );
+        let res = Client::new()
+            .post(url)
+            .header("Authorization", header)
+            .send()
+            .await?;
+        if !res.status().is_success() {
+            return Err(response_error(res).await);
+        }
+        let params = parse_form_urlencoded(&res.text().await?);
+        Ok(UserCredentials {
+            consumer_key: request_token.consumer_key,
+            consumer_secret: request_token.consumer_secret,
+            oauth_token: params.get("oauth_token").cloned().unwrap_or_default(),
+            oauth_token_secret: params
+                .get("oauth_token_secret")
+                .cloned()
+                .unwrap_or_default(),
+        })
     }
 
     pub async fn get_user_by_username(&self, username: &str) -> Result<User> {
@@ -239,6 +737,17 @@ impl Api {
                         .as_str(),
                 );
             }
+            if !opts.expansions.is_empty() {
+                query.append_pair(
+                    "expansions",
+                    opts.expansions
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>()
+                        .join(",")
+                        .as_str(),
+                );
+            }
             if let Some(max_results) = opts.max_results {
                 query.append_pair("max_results", &max_results.to_string());
             }
@@ -248,7 +757,211 @@ impl Api {
                     &end_time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
                 );
             }
+            if let Some(until_id) = opts.until_id {
+                query.append_pair("until_id", &until_id);
+            }
         }
         Ok(ApiResults::get(self.clone(), url))
     }
+
+    /// Connects to the v2 filtered stream and yields matching tweets as they
+    /// arrive. The connection is kept alive across drops and transport
+    /// errors by reconnecting with exponential backoff; see
+    /// [`StreamResults`].
+    pub fn stream_tweets(&self, opts: Option<GetTweetOpts>) -> Result<StreamResults<Tweet>> {
+        let mut url = self.base_url.join("/2/tweets/search/stream")?;
+        if let Some(opts) = opts {
+            let mut query = url.query_pairs_mut();
+            if !opts.tweet_fields.is_empty() {
+                query.append_pair(
+                    "tweet.fields",
+                    opts.tweet_fields
+                        .iter()
+                        .map(|f| f.to_string())
+                        .collect::<Vec<_>>()
+                        .join(",")
+                        .as_str(),
+                );
+            }
+            if !opts.expansions.is_empty() {
+                query.append_pair(
+                    "expansions",
+                    opts.expansions
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>()
+                        .join(",")
+                        .as_str(),
+                );
+            }
+        }
+        Ok(StreamResults::connect(self.clone(), url))
+    }
+
+    /// Likes `tweet_id` as `user_id`. Requires [`UserCredentials`].
+    pub async fn like_tweet(&self, user_id: &str, tweet_id: &str) -> Result<LikeResult> {
+        #[derive(Serialize)]
+        struct Body<'a> {
+            tweet_id: &'a str,
+        }
+        let url = self.base_url.join(&format!("/2/users/{}/likes", user_id))?;
+        Ok(
+            ApiResult::request(self, reqwest::Method::POST, url, Some(&Body { tweet_id }))
+                .data()
+                .await?
+                .unwrap(),
+        )
+    }
+
+    /// Undoes [`Api::like_tweet`]. Requires [`UserCredentials`].
+    pub async fn unlike_tweet(&self, user_id: &str, tweet_id: &str) -> Result<LikeResult> {
+        let url = self
+            .base_url
+            .join(&format!("/2/users/{}/likes/{}", user_id, tweet_id))?;
+        Ok(ApiResult::request(self, reqwest::Method::DELETE, url, None::<&()>)
+            .data()
+            .await?
+            .unwrap())
+    }
+
+    /// Follows `target_user_id` as `user_id`. Requires [`UserCredentials`].
+    pub async fn follow_user(&self, user_id: &str, target_user_id: &str) -> Result<FollowResult> {
+        #[derive(Serialize)]
+        struct Body<'a> {
+            target_user_id: &'a str,
+        }
+        let url = self
+            .base_url
+            .join(&format!("/2/users/{}/following", user_id))?;
+        Ok(ApiResult::request(
+            self,
+            reqwest::Method::POST,
+            url,
+            Some(&Body { target_user_id }),
+        )
+        .data()
+        .await?
+        .unwrap())
+    }
+
+    /// Undoes [`Api::follow_user`]. Requires [`UserCredentials`].
+    pub async fn unfollow_user(&self, user_id: &str, target_user_id: &str) -> Result<FollowResult> {
+        let url = self
+            .base_url
+            .join(&format!("/2/users/{}/following/{}", user_id, target_user_id))?;
+        Ok(
+            ApiResult::request(self, reqwest::Method::DELETE, url, None::<&()>)
+                .data()
+                .await?
+                .unwrap(),
+        )
+    }
+}
+
+fn parse_form_urlencoded(body: &str) -> HashMap<String, String> {
+    url::form_urlencoded::parse(body.as_bytes())
+        .into_owned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tweet(id: &str, text: &str) -> Tweet {
+        Tweet {
+            id: id.to_owned(),
+            text: text.to_owned(),
+            created_at: None,
+            referenced_tweets: Vec::new(),
+            entities: None,
+        }
+    }
+
+    fn referencing(id: &str, text: &str, kind: ReferencedTweetType, referenced_id: &str) -> Tweet {
+        Tweet {
+            referenced_tweets: vec![ReferencedTweet {
+                kind,
+                id: referenced_id.to_owned(),
+            }],
+            ..tweet(id, text)
+        }
+    }
+
+    #[test]
+    fn full_text_substitutes_retweet_text_with_the_original() {
+        let mut includes = HashMap::new();
+        includes.insert(
+            "1".to_owned(),
+            tweet("1", "the original, unsurprisingly longer tweet"),
+        );
+        let retweet = referencing(
+            "2",
+            "RT @original: the original, unsurpri…",
+            ReferencedTweetType::Retweeted,
+            "1",
+        );
+        assert_eq!(
+            retweet.full_text(&includes),
+            "the original, unsurprisingly longer tweet"
+        );
+    }
+
+    #[test]
+    fn full_text_falls_back_to_own_text_when_retweet_is_missing_from_includes() {
+        let retweet = referencing(
+            "2",
+            "RT @original: truncated…",
+            ReferencedTweetType::Retweeted,
+            "missing",
+        );
+        assert_eq!(
+            retweet.full_text(&HashMap::new()),
+            "RT @original: truncated…"
+        );
+    }
+
+    #[test]
+    fn full_text_appends_quoted_tweet_text() {
+        let mut includes = HashMap::new();
+        includes.insert("1".to_owned(), tweet("1", "the quoted tweet"));
+        let quoting = referencing("2", "check this out", ReferencedTweetType::Quoted, "1");
+        assert_eq!(
+            quoting.full_text(&includes),
+            "check this out\n\nQuoting: the quoted tweet"
+        );
+    }
+
+    #[test]
+    fn full_text_unescapes_html_entities() {
+        let t = tweet("1", "a &amp; b &lt;3 &gt; c");
+        assert_eq!(t.full_text(&HashMap::new()), "a & b <3 > c");
+    }
+
+    #[test]
+    fn twitter_error_envelope_parses_the_list_form() {
+        let envelope: TwitterErrorEnvelope = serde_json::from_str(
+            r#"{"errors":[{"title":"Not Found Error","detail":"Could not find tweet.","type":"https://api.twitter.com/2/problems/resource-not-found"}]}"#,
+        )
+        .unwrap();
+        let messages = envelope.into_messages();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].title.as_deref(), Some("Not Found Error"));
+        assert_eq!(messages[0].detail.as_deref(), Some("Could not find tweet."));
+    }
+
+    #[test]
+    fn twitter_error_envelope_parses_the_flat_form() {
+        let envelope: TwitterErrorEnvelope = serde_json::from_str(
+            r#"{"title":"Unauthorized","detail":"Invalid or expired token.","status":401}"#,
+        )
+        .unwrap();
+        let messages = envelope.into_messages();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].title.as_deref(), Some("Unauthorized"));
+        assert_eq!(
+            messages[0].detail.as_deref(),
+            Some("Invalid or expired token.")
+        );
+    }
 }
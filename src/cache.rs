@@ -0,0 +1,132 @@
+//! Disk-backed cache of fetched tweets, keyed by user id.
+
+use crate::api::{self, Api, GetTweetOptsBuilder, Tweet};
+use futures::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CachedUser {
+    tweets: Vec<Tweet>,
+    oldest_id: Option<String>,
+}
+
+/// Caches tweets to a local JSON file, keyed by user id, tracking the oldest
+/// id already on disk so a re-run only needs to backfill tweets older than
+/// that.
+#[derive(Debug)]
+pub struct TweetCache {
+    path: PathBuf,
+    users: HashMap<String, CachedUser>,
+    /// Users whose cached tweets have already been replayed this run, so a
+    /// caller looping [`TweetCache::get_user_tweets`] to backfill further
+    /// history doesn't see the same cached tweets over and over.
+    replayed: HashSet<String>,
+}
+
+impl TweetCache {
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_owned();
+        let users = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            users,
+            replayed: HashSet::new(),
+        }
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        let contents = serde_json::to_string(&self.users).expect("cache should serialize");
+        fs::write(&self.path, contents)
+    }
+
+    fn append(&mut self, user_id: &str, tweet: Tweet) {
+        let entry = self.users.entry(user_id.to_owned()).or_default();
+        entry.oldest_id = Some(tweet.id.clone());
+        entry.tweets.push(tweet);
+        if let Err(err) = self.save() {
+            eprintln!("failed to write tweet cache: {}", err);
+        }
+    }
+
+    /// Yields `user_id`'s tweets: whatever is already cached, followed by
+    /// tweets fetched from `api` older than the oldest cached id (`opts` is
+    /// otherwise the same builder callers would hand to
+    /// [`Api::get_user_tweets`], minus `until_id`, which this fills in).
+    /// Every tweet pulled from the network is appended to the cache and
+    /// flushed to disk as it arrives.
+    pub fn get_user_tweets<'a>(
+        &'a mut self,
+        api: &Api,
+        user_id: &str,
+        mut opts: GetTweetOptsBuilder,
+    ) -> api::Result<CachedTweets<'a>> {
+        let oldest_id = self.users.get(user_id).and_then(|user| user.oldest_id.clone());
+        if let Some(oldest_id) = oldest_id {
+            opts.until_id(Some(oldest_id));
+        }
+        let opts = opts.build().expect("tweet opts should build");
+        let fresh = api.get_user_tweets(user_id, Some(opts))?;
+        let replay = self.replayed.insert(user_id.to_owned());
+        Ok(CachedTweets::new(self, user_id, replay, fresh))
+    }
+}
+
+/// Stream returned by [`TweetCache::get_user_tweets`]: cached tweets first,
+/// then tweets freshly pulled from the API, each written back to the cache
+/// as it's yielded.
+#[pin_project]
+pub struct CachedTweets<'a> {
+    cache: &'a mut TweetCache,
+    user_id: String,
+    cached: std::vec::IntoIter<Tweet>,
+    #[pin]
+    fresh: api::ApiResults<Tweet>,
+}
+
+impl<'a> CachedTweets<'a> {
+    fn new(cache: &'a mut TweetCache, user_id: &str, replay: bool, fresh: api::ApiResults<Tweet>) -> Self {
+        let cached = if replay {
+            cache
+                .users
+                .get(user_id)
+                .map(|user| user.tweets.clone())
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        Self {
+            cache,
+            user_id: user_id.to_owned(),
+            cached: cached.into_iter(),
+            fresh,
+        }
+    }
+}
+
+impl<'a> Stream for CachedTweets<'a> {
+    type Item = api::Result<Tweet>;
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if let Some(tweet) = this.cached.next() {
+            return Poll::Ready(Some(Ok(tweet)));
+        }
+        let mut fresh = this.fresh;
+        match fresh.as_mut().poll_next(cx) {
+            Poll::Ready(Some(Ok(mut tweet))) => {
+                // Retweets arrive truncated; resolve the full text from
+                // this page's `includes` now, since it won't be kept.
+                tweet.text = tweet.full_text(fresh.includes());
+                this.cache.append(this.user_id, tweet.clone());
+                Poll::Ready(Some(Ok(tweet)))
+            }
+            other => other,
+        }
+    }
+}
@@ -8,8 +8,11 @@ extern crate derive_builder;
 extern crate pin_project;
 
 pub mod api;
+mod cache;
+mod oauth;
 
-use api::{Api, GetTweetOptsBuilder, Tweet, TweetFields};
+use api::{Api, BearerToken, Expansion, GetTweetOptsBuilder, Tweet, TweetFields};
+use cache::TweetCache;
 use chrono::{DateTime, Duration, Utc};
 use futures::prelude::*;
 use std::env;
@@ -18,6 +21,7 @@ const TWITTER_HANDLE: &str = "elonmusk";
 const MIN_TWEETS_TO_ANALYZE: usize = 1000;
 const POOPS_PER_DAY: f64 = 3.;
 const TWEET_SESSION_TIMEOUT_SECS: i64 = 15 * 60;
+const TWEET_CACHE_PATH: &str = "tweet_cache.json";
 
 #[derive(Clone, Debug)]
 pub struct TweetSession {
@@ -96,39 +100,46 @@ impl PoopPeriod {
 async fn main() {
     let token =
         env::var("TWITTER_API_BEARER_TOKEN").expect("TWITTER_API_BEARER_TOKEN should be set");
-    let api = Api::new(token.into());
+    let api = Api::new(BearerToken::from(token));
 
     let elonmusk = api
         .get_user_by_username(TWITTER_HANDLE)
         .await
         .expect("retrieving user shouldn't fail");
 
+    let mut cache = TweetCache::load(TWEET_CACHE_PATH);
     let mut tweet_num = 0;
-    let mut end_time = Utc::now();
     let mut poop_period: Option<PoopPeriod> = None;
     let mut tweet_session: Option<TweetSession> = None;
     let mut poop_tweets = 0;
     let mut non_poop_tweets = 0;
 
     while tweet_num < MIN_TWEETS_TO_ANALYZE {
-        let mut tweets = api
+        let mut tweets = cache
             .get_user_tweets(
+                &api,
                 &elonmusk.id,
-                Some(
-                    GetTweetOptsBuilder::default()
-                        .tweet_fields([TweetFields::CreatedAt].into())
-                        .max_results(Some(100))
-                        .end_time(Some(end_time))
-                        .build()
-                        .unwrap(),
-                ),
+                {
+                    let mut opts = GetTweetOptsBuilder::default();
+                    opts.tweet_fields(
+                        [
+                            TweetFields::CreatedAt,
+                            TweetFields::ReferencedTweets,
+                            TweetFields::Entities,
+                        ]
+                        .into(),
+                    )
+                    .expansions([Expansion::ReferencedTweetsId].into())
+                    .max_results(Some(100));
+                    opts
+                },
             )
             .expect("tweet request should be valid");
         while let Some(tweet) = tweets.next().await {
             let tweet = tweet.expect("retrieving tweets shouldn't fail");
-            println!("{:?}", tweet);
+            println!("{}", tweet.text);
             tweet_num += 1;
-            end_time = tweet
+            let end_time: DateTime<Utc> = tweet
                 .created_at
                 .expect("tweet should have created at")
                 .into();